@@ -11,15 +11,21 @@ use bitcoin::util::base58::Error::InvalidLength;
 use bitcoin::util::bip32::{
     ChainCode, ChildNumber, Error as Bip32Error, ExtendedPrivKey, ExtendedPubKey, Fingerprint,
 };
+use bitcoin::hashes::Hash;
 use bitcoin::Network;
 use byteorder::BigEndian;
 use byteorder::ByteOrder;
 
 use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
 
-pub struct Bip32DeterministicPrivateKey(ExtendedPrivKey);
+/// The second field remembers which SLIP-132 string variant (if any) this key
+/// was parsed from, so `to_string()` round-trips `ypub`/`zpub`/etc. instead of
+/// silently normalizing every key back to `xpub`/`xprv`.
+pub struct Bip32DeterministicPrivateKey(ExtendedPrivKey, Option<Bip32VersionPrefix>);
 
-pub struct Bip32DeterministicPublicKey(ExtendedPubKey);
+pub struct Bip32DeterministicPublicKey(ExtendedPubKey, Option<Bip32VersionPrefix>);
 
 fn transform_bip32_error(err: Bip32Error) -> KeyError {
     match err {
@@ -37,7 +43,7 @@ impl Bip32DeterministicPrivateKey {
     pub fn from_seed(seed: &[u8]) -> Result<Self> {
         let epk =
             ExtendedPrivKey::new_master(Network::Bitcoin, seed).map_err(transform_bip32_error)?;
-        Ok(Bip32DeterministicPrivateKey(epk))
+        Ok(Bip32DeterministicPrivateKey(epk, None))
     }
 }
 
@@ -53,7 +59,7 @@ impl Derive for Bip32DeterministicPrivateKey {
                 .map_err(transform_bip32_error)?;
         }
 
-        Ok(Bip32DeterministicPrivateKey(extended_key))
+        Ok(Bip32DeterministicPrivateKey(extended_key, self.1))
     }
 }
 
@@ -69,7 +75,110 @@ impl Derive for Bip32DeterministicPublicKey {
                 .map_err(transform_bip32_error)?;
         }
 
-        Ok(Bip32DeterministicPublicKey(extended_key))
+        Ok(Bip32DeterministicPublicKey(extended_key, self.1))
+    }
+}
+
+/// A single `ChainPath` step, classified up front as `Normal` or `Hardened`,
+/// matching the separation `hdwallet`'s `key_chain`/`chain_path` modules use
+/// to keep public derivation from ever touching a hardened index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyIndex {
+    Normal(u32),
+    Hardened(u32),
+}
+
+impl KeyIndex {
+    pub fn is_normal(self) -> bool {
+        matches!(self, KeyIndex::Normal(_))
+    }
+
+    pub fn is_hardened(self) -> bool {
+        matches!(self, KeyIndex::Hardened(_))
+    }
+
+    /// The raw BIP32 child number, with the hardened bit folded in if applicable.
+    pub fn raw_index(self) -> u32 {
+        match self {
+            KeyIndex::Normal(index) => index,
+            KeyIndex::Hardened(index) => index | 0x8000_0000,
+        }
+    }
+}
+
+impl FromStr for KeyIndex {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (digits, hardened) = match s.strip_suffix('\'').or_else(|| s.strip_suffix('h')) {
+            Some(digits) => (digits, true),
+            None => (s, false),
+        };
+        let index: u32 = digits
+            .parse()
+            .map_err(|_| KeyError::InvalidChildNumber)?;
+        if index >= 0x8000_0000 {
+            return Err(KeyError::InvalidChildNumber.into());
+        }
+
+        Ok(if hardened {
+            KeyIndex::Hardened(index)
+        } else {
+            KeyIndex::Normal(index)
+        })
+    }
+}
+
+/// A parsed BIP32 derivation path that keeps every junction's hardened/normal
+/// classification available before any derivation is attempted.
+#[derive(Debug, Clone)]
+pub struct ChainPath(Vec<KeyIndex>);
+
+impl ChainPath {
+    pub fn iter(&self) -> impl Iterator<Item = &KeyIndex> {
+        self.0.iter()
+    }
+
+    /// True when every junction is non-hardened, i.e. safe to derive from a public key.
+    pub fn is_normal_only(&self) -> bool {
+        self.0.iter().all(KeyIndex::is_normal)
+    }
+}
+
+impl FromStr for ChainPath {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let path = s.trim_start_matches("m/").trim_start_matches('/');
+        if path.is_empty() {
+            return Ok(ChainPath(vec![]));
+        }
+
+        let indices = path
+            .split('/')
+            .map(KeyIndex::from_str)
+            .collect::<Result<Vec<KeyIndex>>>()?;
+        Ok(ChainPath(indices))
+    }
+}
+
+impl Bip32DeterministicPublicKey {
+    /// Derive along a [`ChainPath`], rejecting up front if any junction is
+    /// hardened instead of failing deep inside `ckd_pub` partway through derivation.
+    pub fn derive_chain_path(&self, path: &ChainPath) -> Result<Self> {
+        if !path.is_normal_only() {
+            return Err(KeyError::CannotDeriveFromHardenedKey.into());
+        }
+
+        let mut extended_key = self.0.clone();
+        for index in path.iter() {
+            let child_number = ChildNumber::from(index.raw_index());
+            extended_key = extended_key
+                .ckd_pub(&SECP256K1_ENGINE, child_number)
+                .map_err(transform_bip32_error)?;
+        }
+
+        Ok(Bip32DeterministicPublicKey(extended_key, self.1))
     }
 }
 
@@ -80,7 +189,7 @@ impl DeterministicPrivateKey for Bip32DeterministicPrivateKey {
     fn from_seed(seed: &[u8]) -> Result<Self> {
         let esk =
             ExtendedPrivKey::new_master(Network::Bitcoin, seed).map_err(transform_bip32_error)?;
-        Ok(Bip32DeterministicPrivateKey(esk))
+        Ok(Bip32DeterministicPrivateKey(esk, None))
     }
 
     fn private_key(&self) -> Self::PrivateKey {
@@ -89,7 +198,7 @@ impl DeterministicPrivateKey for Bip32DeterministicPrivateKey {
 
     fn deterministic_public_key(&self) -> Self::DeterministicPublicKey {
         let pk = ExtendedPubKey::from_private(&SECP256K1_ENGINE, &self.0);
-        Bip32DeterministicPublicKey(pk)
+        Bip32DeterministicPublicKey(pk, self.1.map(Bip32VersionPrefix::to_public))
     }
 }
 
@@ -101,6 +210,49 @@ impl DeterministicPublicKey for Bip32DeterministicPublicKey {
     }
 }
 
+impl Bip32DeterministicPublicKey {
+    /// Import an extended public key string (e.g. an account-level xpub) to
+    /// continue deriving only its non-hardened child levels, for watch-only wallets.
+    pub fn from_xpub_str(s: &str) -> Result<Self> {
+        Self::from_str(s)
+    }
+
+    /// The BIP32 key identifier: `RIPEMD160(SHA256(compressed_pubkey))`.
+    pub fn identifier(&self) -> [u8; 20] {
+        self.0.identifier().into_inner()
+    }
+
+    /// The first 4 bytes of [`identifier`](Self::identifier), used to match a
+    /// derived child back to its parent without re-deriving.
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.0.fingerprint()
+    }
+}
+
+impl Bip32DeterministicPrivateKey {
+    /// "Neuter" this key: derive the watch-only public key at this key's current
+    /// depth, preserving `parent_fingerprint` and `child_number` so a watch-only
+    /// wallet can resume non-hardened derivation from the public side.
+    pub fn neuter(&self) -> Bip32DeterministicPublicKey {
+        self.deterministic_public_key()
+    }
+
+    /// Alias of [`neuter`](Self::neuter) matching the `DeterministicPrivateKey` vocabulary.
+    pub fn to_deterministic_public_key(&self) -> Bip32DeterministicPublicKey {
+        self.neuter()
+    }
+
+    /// Delegates to the matching public key's identifier.
+    pub fn identifier(&self) -> [u8; 20] {
+        self.deterministic_public_key().identifier()
+    }
+
+    /// Delegates to the matching public key's fingerprint.
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.deterministic_public_key().fingerprint()
+    }
+}
+
 impl Ss58Codec for Bip32DeterministicPublicKey {
     fn from_ss58check_with_version(s: &str) -> Result<(Self, Vec<u8>)> {
         let data = base58::from_check(s)?;
@@ -123,7 +275,7 @@ impl Ss58Codec for Bip32DeterministicPublicKey {
 
         let mut network = [0; 4];
         network.copy_from_slice(&data[0..4]);
-        Ok((Bip32DeterministicPublicKey(epk), network.to_vec()))
+        Ok((Bip32DeterministicPublicKey(epk, None), network.to_vec()))
     }
 
     fn to_ss58check_with_version(&self, version: &[u8]) -> String {
@@ -168,7 +320,7 @@ impl Ss58Codec for Bip32DeterministicPrivateKey {
         };
         let mut network = [0; 4];
         network.copy_from_slice(&data[0..4]);
-        Ok((Bip32DeterministicPrivateKey(epk), network.to_vec()))
+        Ok((Bip32DeterministicPrivateKey(epk, None), network.to_vec()))
     }
 
     fn to_ss58check_with_version(&self, version: &[u8]) -> String {
@@ -188,9 +340,153 @@ impl Ss58Codec for Bip32DeterministicPrivateKey {
     }
 }
 
+/// The SLIP-132 version prefixes for extended keys: the 4 leading bytes that
+/// tell a `xprv`/`xpub`-style string apart from its `ypub`/`zpub`/`tpub` cousins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bip32VersionPrefix {
+    XprvMainnet,
+    XpubMainnet,
+    YprvMainnet,
+    YpubMainnet,
+    ZprvMainnet,
+    ZpubMainnet,
+    TprvTestnet,
+    TpubTestnet,
+}
+
+impl Bip32VersionPrefix {
+    const ALL: [Bip32VersionPrefix; 8] = [
+        Bip32VersionPrefix::XprvMainnet,
+        Bip32VersionPrefix::XpubMainnet,
+        Bip32VersionPrefix::YprvMainnet,
+        Bip32VersionPrefix::YpubMainnet,
+        Bip32VersionPrefix::ZprvMainnet,
+        Bip32VersionPrefix::ZpubMainnet,
+        Bip32VersionPrefix::TprvTestnet,
+        Bip32VersionPrefix::TpubTestnet,
+    ];
+
+    /// The 4-byte version prefix written at the start of the serialized key.
+    pub fn version_bytes(self) -> [u8; 4] {
+        match self {
+            Bip32VersionPrefix::XprvMainnet => [0x04, 0x88, 0xAD, 0xE4],
+            Bip32VersionPrefix::XpubMainnet => [0x04, 0x88, 0xB2, 0x1E],
+            Bip32VersionPrefix::YprvMainnet => [0x04, 0x9D, 0x78, 0x78],
+            Bip32VersionPrefix::YpubMainnet => [0x04, 0x9D, 0x7C, 0xB2],
+            Bip32VersionPrefix::ZprvMainnet => [0x04, 0xB2, 0x43, 0x0C],
+            Bip32VersionPrefix::ZpubMainnet => [0x04, 0xB2, 0x47, 0x46],
+            Bip32VersionPrefix::TprvTestnet => [0x04, 0x35, 0x83, 0x94],
+            Bip32VersionPrefix::TpubTestnet => [0x04, 0x35, 0x87, 0xCF],
+        }
+    }
+
+    /// Recover the registry entry matching a decoded key's leading 4 bytes.
+    pub fn from_version_bytes(bytes: [u8; 4]) -> Result<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|prefix| prefix.version_bytes() == bytes)
+            .ok_or_else(|| KeyError::InvalidVersion.into())
+    }
+
+    pub fn network(self) -> Network {
+        match self {
+            Bip32VersionPrefix::TprvTestnet | Bip32VersionPrefix::TpubTestnet => Network::Testnet,
+            _ => Network::Bitcoin,
+        }
+    }
+
+    pub fn is_private(self) -> bool {
+        matches!(
+            self,
+            Bip32VersionPrefix::XprvMainnet
+                | Bip32VersionPrefix::YprvMainnet
+                | Bip32VersionPrefix::ZprvMainnet
+                | Bip32VersionPrefix::TprvTestnet
+        )
+    }
+
+    fn default_for(network: Network, is_private: bool) -> Self {
+        match (network, is_private) {
+            (Network::Bitcoin, true) => Bip32VersionPrefix::XprvMainnet,
+            (Network::Bitcoin, false) => Bip32VersionPrefix::XpubMainnet,
+            (_, true) => Bip32VersionPrefix::TprvTestnet,
+            (_, false) => Bip32VersionPrefix::TpubTestnet,
+        }
+    }
+
+    /// The public-key counterpart of a private-key variant (e.g. `yprv` -> `ypub`);
+    /// a no-op for variants that are already public.
+    fn to_public(self) -> Self {
+        match self {
+            Bip32VersionPrefix::XprvMainnet => Bip32VersionPrefix::XpubMainnet,
+            Bip32VersionPrefix::YprvMainnet => Bip32VersionPrefix::YpubMainnet,
+            Bip32VersionPrefix::ZprvMainnet => Bip32VersionPrefix::ZpubMainnet,
+            Bip32VersionPrefix::TprvTestnet => Bip32VersionPrefix::TpubTestnet,
+            other => other,
+        }
+    }
+}
+
+fn version_bytes_of(version: &[u8]) -> Result<[u8; 4]> {
+    version
+        .try_into()
+        .map_err(|_| KeyError::InvalidVersion.into())
+}
+
+impl fmt::Display for Bip32DeterministicPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prefix = self
+            .1
+            .unwrap_or_else(|| Bip32VersionPrefix::default_for(self.0.network, false));
+        write!(f, "{}", self.to_ss58check_with_version(&prefix.version_bytes()))
+    }
+}
+
+impl FromStr for Bip32DeterministicPublicKey {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (mut key, version) = Self::from_ss58check_with_version(s)?;
+        let prefix = Bip32VersionPrefix::from_version_bytes(version_bytes_of(&version)?)?;
+        if prefix.is_private() {
+            return Err(KeyError::InvalidVersion.into());
+        }
+        key.0.network = prefix.network();
+        key.1 = Some(prefix);
+        Ok(key)
+    }
+}
+
+impl fmt::Display for Bip32DeterministicPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prefix = self
+            .1
+            .unwrap_or_else(|| Bip32VersionPrefix::default_for(self.0.network, true));
+        write!(f, "{}", self.to_ss58check_with_version(&prefix.version_bytes()))
+    }
+}
+
+impl FromStr for Bip32DeterministicPrivateKey {
+    type Err = failure::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (mut key, version) = Self::from_ss58check_with_version(s)?;
+        let prefix = Bip32VersionPrefix::from_version_bytes(version_bytes_of(&version)?)?;
+        if !prefix.is_private() {
+            return Err(KeyError::InvalidVersion.into());
+        }
+        key.0.network = prefix.network();
+        key.1 = Some(prefix);
+        Ok(key)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::ecc::DeterministicPrivateKey;
     use crate::{Bip32DeterministicPrivateKey, Bip32DeterministicPublicKey, Derive, DerivePath};
+    use super::{ChainPath, KeyIndex};
     use std::str::FromStr;
 
     #[test]
@@ -271,4 +567,121 @@ mod tests {
         assert_eq!(format!("{}", err), "invalid base58 character 0x6c");
         */
     }
+
+    // BIP32 test vector 1, seed 000102030405060708090a0b0c0d0e0f.
+    const BIP32_TEST_VECTOR_1_SEED: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+    const BIP32_TEST_VECTOR_1_MASTER_XPRV: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+    const BIP32_TEST_VECTOR_1_MASTER_XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
+    #[test]
+    fn to_string_round_trips_master_xprv_and_xpub() {
+        let esk = Bip32DeterministicPrivateKey::from_seed(&BIP32_TEST_VECTOR_1_SEED).unwrap();
+        assert_eq!(esk.to_string(), BIP32_TEST_VECTOR_1_MASTER_XPRV);
+
+        let epk = esk.deterministic_public_key();
+        assert_eq!(epk.to_string(), BIP32_TEST_VECTOR_1_MASTER_XPUB);
+    }
+
+    #[test]
+    fn from_str_round_trips_master_xprv_and_xpub() {
+        let esk = Bip32DeterministicPrivateKey::from_str(BIP32_TEST_VECTOR_1_MASTER_XPRV).unwrap();
+        assert_eq!(esk.to_string(), BIP32_TEST_VECTOR_1_MASTER_XPRV);
+
+        let epk = Bip32DeterministicPublicKey::from_str(BIP32_TEST_VECTOR_1_MASTER_XPUB).unwrap();
+        assert_eq!(epk.to_string(), BIP32_TEST_VECTOR_1_MASTER_XPUB);
+    }
+
+    #[test]
+    fn from_str_rejects_priv_pub_version_mismatch() {
+        assert!(Bip32DeterministicPrivateKey::from_str(BIP32_TEST_VECTOR_1_MASTER_XPUB).is_err());
+        assert!(Bip32DeterministicPublicKey::from_str(BIP32_TEST_VECTOR_1_MASTER_XPRV).is_err());
+    }
+
+    // The BIP32 test vector 1 master key, re-encoded with the BIP84 `zpub`
+    // version prefix instead of `xpub` (same depth/fingerprint/chaincode/pubkey).
+    const BIP32_TEST_VECTOR_1_MASTER_ZPUB: &str = "zpub6jftahH18ngZxUuv6oSniLNrBCSSE1B4EEU59bwTCEt8x6aS6b2mdfLxbS4QS53g85SWWP6wexqeer516433gYpZQoJie2tcMYdJ1SYYYAL";
+
+    #[test]
+    fn from_str_round_trips_zpub_without_normalizing_to_xpub() {
+        let epk = Bip32DeterministicPublicKey::from_str(BIP32_TEST_VECTOR_1_MASTER_ZPUB).unwrap();
+        assert_eq!(epk.to_string(), BIP32_TEST_VECTOR_1_MASTER_ZPUB);
+    }
+
+    #[test]
+    fn identifier_and_fingerprint_match_master_key_test_vector() {
+        let esk = Bip32DeterministicPrivateKey::from_seed(&BIP32_TEST_VECTOR_1_SEED).unwrap();
+        let epk = esk.deterministic_public_key();
+
+        let expected_identifier =
+            hex::decode("3442193e1bb70916e914552172cd4e2dbc9df811").unwrap();
+        assert_eq!(epk.identifier().to_vec(), expected_identifier);
+        assert_eq!(epk.fingerprint()[..], expected_identifier[..4]);
+
+        assert_eq!(esk.identifier().to_vec(), expected_identifier);
+        assert_eq!(esk.fingerprint()[..], expected_identifier[..4]);
+    }
+
+    #[test]
+    fn neuter_supports_watch_only_public_derivation() {
+        let esk = Bip32DeterministicPrivateKey::from_seed(&BIP32_TEST_VECTOR_1_SEED).unwrap();
+        let account_sk = esk
+            .derive(DerivePath::from_str("m/44'/0'/0'").unwrap().into_iter())
+            .unwrap();
+
+        let account_pk = account_sk.neuter();
+        assert_eq!(account_pk.to_string(), account_sk.to_deterministic_public_key().to_string());
+
+        let account_pk_imported =
+            Bip32DeterministicPublicKey::from_xpub_str(&account_pk.to_string()).unwrap();
+        let child_pk_from_account = account_pk_imported
+            .derive(DerivePath::from_str("0/0").unwrap().into_iter())
+            .unwrap();
+
+        let child_pk_from_root = esk
+            .derive(DerivePath::from_str("m/44'/0'/0'/0/0").unwrap().into_iter())
+            .unwrap()
+            .deterministic_public_key();
+
+        assert_eq!(child_pk_from_account.to_string(), child_pk_from_root.to_string());
+    }
+
+    #[test]
+    fn key_index_parses_apostrophe_and_h_hardened_notation() {
+        assert_eq!(KeyIndex::from_str("0").unwrap(), KeyIndex::Normal(0));
+        assert_eq!(KeyIndex::from_str("44'").unwrap(), KeyIndex::Hardened(44));
+        assert_eq!(KeyIndex::from_str("44h").unwrap(), KeyIndex::Hardened(44));
+    }
+
+    #[test]
+    fn chain_path_is_normal_only_reflects_hardened_junctions() {
+        assert!(ChainPath::from_str("0/0").unwrap().is_normal_only());
+        assert!(!ChainPath::from_str("44'/0'/0'").unwrap().is_normal_only());
+    }
+
+    #[test]
+    fn derive_chain_path_rejects_hardened_junctions_up_front() {
+        let esk = Bip32DeterministicPrivateKey::from_seed(&BIP32_TEST_VECTOR_1_SEED).unwrap();
+        let account_pk = esk
+            .derive(DerivePath::from_str("m/44'/0'/0'").unwrap().into_iter())
+            .unwrap()
+            .neuter();
+
+        assert!(account_pk
+            .derive_chain_path(&ChainPath::from_str("0'/0").unwrap())
+            .is_err());
+
+        let child = account_pk
+            .derive_chain_path(&ChainPath::from_str("0/0").unwrap())
+            .unwrap();
+        assert_eq!(
+            child.to_string(),
+            account_pk
+                .derive(DerivePath::from_str("0/0").unwrap().into_iter())
+                .unwrap()
+                .to_string()
+        );
+    }
 }
\ No newline at end of file
@@ -0,0 +1,28 @@
+//! TokenCore Primitive
+//! This is an abstract package to define basic keypair/address data structure.
+
+extern crate failure;
+
+mod constant;
+mod derive;
+mod ecc;
+mod secp256k1;
+
+pub mod bip32;
+pub mod slip10;
+
+pub use constant::SECP256K1_ENGINE;
+pub use derive::{Derive, DeriveJunction, DerivePath};
+pub use ecc::{
+    DeterministicPrivateKey, DeterministicPublicKey, KeyError, Pair, PrivateKey, Public,
+    PublicKey, Ss58Codec,
+};
+pub use secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey};
+
+pub use bip32::{Bip32DeterministicPrivateKey, Bip32DeterministicPublicKey, Bip32VersionPrefix};
+pub use slip10::{
+    slip10_master_key, Ed25519DeterministicPrivateKey, Ed25519DeterministicPublicKey,
+    Ed25519PrivateKey, Ed25519PublicKey, Slip10Curve,
+};
+
+pub type Result<T> = std::result::Result<T, failure::Error>;
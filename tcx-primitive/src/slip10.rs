@@ -0,0 +1,252 @@
+use super::Result;
+
+use crate::ecc::{
+    DeterministicPrivateKey, DeterministicPublicKey, KeyError, PrivateKey as PrivateKeyTrait,
+    PublicKey as PublicKeyTrait,
+};
+use crate::{Derive, DeriveJunction};
+
+use bitcoin::util::bip32::ChildNumber;
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+
+use std::convert::TryInto;
+
+type HmacSha512 = Hmac<Sha512>;
+
+const ED25519_SEED_SALT: &[u8] = b"ed25519 seed";
+
+/// The curves this SLIP-0010 subsystem knows how to derive. `Secp256k1` has
+/// its own BIP32 implementation in `bip32.rs`; this module covers the rest.
+/// Nist256p1 isn't implemented yet, so it has no variant here — add one
+/// alongside its implementation rather than returning `UnsupportedCurve` for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slip10Curve {
+    Ed25519,
+}
+
+/// Derive a SLIP-0010 master key for the given curve.
+pub fn slip10_master_key(
+    curve: Slip10Curve,
+    seed: &[u8],
+) -> Result<Ed25519DeterministicPrivateKey> {
+    match curve {
+        Slip10Curve::Ed25519 => Ed25519DeterministicPrivateKey::from_seed(seed),
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_varkey(key).expect("HMAC accepts a key of any length");
+    mac.update(data);
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+fn split_i(i: &[u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&i[..32]);
+    right.copy_from_slice(&i[32..]);
+    (left, right)
+}
+
+fn ed25519_public_key(secret_key: &[u8; 32]) -> [u8; 32] {
+    let secret = ed25519_dalek::SecretKey::from_bytes(secret_key)
+        .expect("SLIP-10 always produces a valid 32-byte ed25519 secret key");
+    let expanded = ed25519_dalek::ExpandedSecretKey::from(&secret);
+    ed25519_dalek::PublicKey::from(&expanded).to_bytes()
+}
+
+/// A SLIP-0010 ed25519 extended private key. Derivation is hardened-only:
+/// ed25519 has no additive trick for deriving children from a public key.
+#[derive(Clone)]
+pub struct Ed25519DeterministicPrivateKey {
+    secret_key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+/// The watch-only half of an [`Ed25519DeterministicPrivateKey`]. It cannot
+/// derive further children; SLIP-0010 ed25519 children are always hardened.
+#[derive(Clone)]
+pub struct Ed25519DeterministicPublicKey {
+    public_key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+/// The raw 32-byte ed25519 private scalar produced by SLIP-0010 derivation.
+#[derive(Clone)]
+pub struct Ed25519PrivateKey([u8; 32]);
+
+impl Ed25519PrivateKey {
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl PrivateKeyTrait for Ed25519PrivateKey {
+    type PublicKey = Ed25519PublicKey;
+
+    fn public_key(&self) -> Self::PublicKey {
+        Ed25519PublicKey(ed25519_public_key(&self.0))
+    }
+}
+
+/// The raw 32-byte ed25519 public point produced by SLIP-0010 derivation.
+#[derive(Clone)]
+pub struct Ed25519PublicKey([u8; 32]);
+
+impl Ed25519PublicKey {
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl PublicKeyTrait for Ed25519PublicKey {}
+
+impl Ed25519DeterministicPrivateKey {
+    /// Construct the master key: `HMAC-SHA512(key = "ed25519 seed", data = seed)`,
+    /// splitting the 64-byte result into a 32-byte key and 32-byte chain code.
+    pub fn from_seed(seed: &[u8]) -> Result<Self> {
+        let (secret_key, chain_code) = split_i(&hmac_sha512(ED25519_SEED_SALT, seed));
+        Ok(Ed25519DeterministicPrivateKey {
+            secret_key,
+            chain_code,
+        })
+    }
+
+    fn derive_hardened_child(&self, index: u32) -> Self {
+        let mut data = Vec::with_capacity(1 + 32 + 4);
+        data.push(0x00);
+        data.extend_from_slice(&self.secret_key);
+        data.extend_from_slice(&(0x8000_0000 | index).to_be_bytes());
+
+        let (secret_key, chain_code) = split_i(&hmac_sha512(&self.chain_code, &data));
+        Ed25519DeterministicPrivateKey {
+            secret_key,
+            chain_code,
+        }
+    }
+}
+
+impl Derive for Ed25519DeterministicPrivateKey {
+    fn derive<T: Iterator<Item = DeriveJunction>>(&self, path: T) -> Result<Self> {
+        let mut key = self.clone();
+        for junction in path {
+            let child_number: ChildNumber = junction.try_into()?;
+            match child_number {
+                ChildNumber::Hardened { index } => key = key.derive_hardened_child(index),
+                ChildNumber::Normal { .. } => return Err(KeyError::InvalidChildNumber.into()),
+            }
+        }
+        Ok(key)
+    }
+}
+
+impl Derive for Ed25519DeterministicPublicKey {
+    fn derive<T: Iterator<Item = DeriveJunction>>(&self, _path: T) -> Result<Self> {
+        // Every SLIP-0010 ed25519 step is hardened, and a hardened child can
+        // never be derived from a public key.
+        Err(KeyError::CannotDeriveFromHardenedKey.into())
+    }
+}
+
+impl DeterministicPrivateKey for Ed25519DeterministicPrivateKey {
+    type DeterministicPublicKey = Ed25519DeterministicPublicKey;
+    type PrivateKey = Ed25519PrivateKey;
+
+    fn from_seed(seed: &[u8]) -> Result<Self> {
+        Ed25519DeterministicPrivateKey::from_seed(seed)
+    }
+
+    fn private_key(&self) -> Self::PrivateKey {
+        Ed25519PrivateKey(self.secret_key)
+    }
+
+    fn deterministic_public_key(&self) -> Self::DeterministicPublicKey {
+        Ed25519DeterministicPublicKey {
+            public_key: ed25519_public_key(&self.secret_key),
+            chain_code: self.chain_code,
+        }
+    }
+}
+
+impl DeterministicPublicKey for Ed25519DeterministicPublicKey {
+    type PublicKey = Ed25519PublicKey;
+
+    fn public_key(&self) -> Self::PublicKey {
+        Ed25519PublicKey(self.public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ed25519DeterministicPrivateKey;
+    use crate::ecc::DeterministicPrivateKey;
+    use crate::{Derive, DeriveJunction};
+    use std::convert::TryFrom;
+
+    // SLIP-0010 official ed25519 test vector 1, seed 000102030405060708090a0b0c0d0e0f.
+    const SEED: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+
+    fn hardened(index: u32) -> DeriveJunction {
+        DeriveJunction::try_from(0x8000_0000 | index).unwrap()
+    }
+
+    #[test]
+    fn derives_master_key_test_vector() {
+        let esk = Ed25519DeterministicPrivateKey::from_seed(&SEED).unwrap();
+        assert_eq!(
+            hex::encode(esk.private_key().to_bytes()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex::encode(esk.deterministic_public_key().public_key().to_bytes()),
+            "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"
+        );
+    }
+
+    #[test]
+    fn derives_hardened_child_test_vectors() {
+        let esk = Ed25519DeterministicPrivateKey::from_seed(&SEED).unwrap();
+
+        let m0h = esk.derive(vec![hardened(0)].into_iter()).unwrap();
+        assert_eq!(
+            hex::encode(m0h.private_key().to_bytes()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert_eq!(
+            hex::encode(m0h.deterministic_public_key().public_key().to_bytes()),
+            "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c"
+        );
+
+        let m0h1h = esk
+            .derive(vec![hardened(0), hardened(1)].into_iter())
+            .unwrap();
+        assert_eq!(
+            hex::encode(m0h1h.private_key().to_bytes()),
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2"
+        );
+        assert_eq!(
+            hex::encode(m0h1h.deterministic_public_key().public_key().to_bytes()),
+            "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187"
+        );
+    }
+
+    #[test]
+    fn rejects_non_hardened_derivation() {
+        let esk = Ed25519DeterministicPrivateKey::from_seed(&SEED).unwrap();
+        let normal = DeriveJunction::try_from(0u32).unwrap();
+        assert!(esk.derive(vec![normal].into_iter()).is_err());
+    }
+
+    #[test]
+    fn public_key_cannot_derive_children() {
+        let esk = Ed25519DeterministicPrivateKey::from_seed(&SEED).unwrap();
+        let epk = esk.deterministic_public_key();
+        assert!(epk.derive(vec![hardened(0)].into_iter()).is_err());
+    }
+}